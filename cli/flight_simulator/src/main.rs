@@ -1,62 +1,172 @@
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
+    cursor::{Hide, MoveLeft, MoveTo, Show},
     event::{poll, read, Event, KeyCode, KeyModifiers},
     execute,
     style::{Print, ResetColor},
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
-use rand::{Rng, seq::SliceRandom};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 use std::{
+    env, fs,
     io::{stdout, Write},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
+mod net;
+mod nn;
+mod trainer;
+
+const LEADERBOARD_URL: &str = "http://127.0.0.1:8080";
+
+#[derive(Serialize)]
+struct ScoreSubmission<'a> {
+    name: &'a str,
+    points: u32,
+}
+
+#[derive(Deserialize)]
+struct LeaderboardEntry {
+    name: String,
+    points: u32,
+}
+
 const PLANE: char = '🛩';
 const GROUND: char = '▁';
-const JUMP_HEIGHT: usize = 5;
 const GAME_WIDTH: usize = 80;
 const GAME_HEIGHT: usize = 10;
 const GAME_SPEED: u64 = 50; // Lower value means faster game
+const PLANE_X: usize = 2;
+const REPLAY_LOG_PATH: &str = "replay.log";
+
+// Multiplayer draws the opponent's plane a few columns behind ours so the
+// two are never on top of each other.
+const OPPONENT_PLANE: char = '✈';
+const OPPONENT_X: usize = PLANE_X + 4;
+const MATCHMAKING_URL: &str = "ws://127.0.0.1:3030/play";
+
+// Jump physics: gravity pulls the plane down every tick, and holding jump
+// applies an upward boost for a few ticks. `MIN_BOOST_TICKS` guarantees a
+// tap still produces a short hop; `MAX_BOOST_TICKS` caps how high holding
+// the key can carry the plane.
+const GRAVITY: i32 = 1;
+const BOOST_VELOCITY: i32 = 2;
+const MIN_BOOST_TICKS: u32 = 2;
+const MAX_BOOST_TICKS: u32 = 6;
+const MAX_PLANE_HEIGHT: i32 = (GAME_HEIGHT - 2) as i32;
 
 const BUILDINGS: [&str; 5] = ["🏠", "🏢", "🏫", "🏛️", "🏰"];
 
+/// A single tick's worth of player input. The game is a pure function of
+/// (seed, tick, input sequence), so recording this is all a replay needs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Input {
+    Jump,
+    Quit,
+    None,
+}
+
+impl Input {
+    fn to_char(self) -> char {
+        match self {
+            Input::Jump => 'J',
+            Input::Quit => 'Q',
+            Input::None => 'N',
+        }
+    }
+
+    fn from_char(c: char) -> Input {
+        match c {
+            'J' => Input::Jump,
+            'Q' => Input::Quit,
+            _ => Input::None,
+        }
+    }
+}
+
 struct Game {
-    plane_y: usize,
-    jumping: bool,
+    plane_y: i32,
+    velocity: i32,
+    boosting: bool,
+    boost_ticks: u32,
     obstacles: Vec<(usize, &'static str)>,
     score: u32,
-    last_update: Instant,
-    buffer: Vec<Vec<char>>,
+    // Two owned grids that swap roles each frame so drawing never has to
+    // reallocate: `switch` selects which of `buffers` is the "front" (the
+    // frame we just drew) versus the "back" (the previously rendered frame).
+    buffers: [Vec<Vec<char>>; 2],
+    switch: bool,
+    force_repaint: bool,
     ground_offset: usize,
+    rng: StdRng,
+    seed: u64,
+    input_log: Vec<Input>,
 }
 
 impl Game {
-    fn new() -> Self {
+    fn new(seed: u64) -> Self {
         Game {
             plane_y: 0,
-            jumping: false,
+            velocity: 0,
+            boosting: false,
+            boost_ticks: 0,
             obstacles: vec![],
             score: 0,
-            last_update: Instant::now(),
-            buffer: vec![vec![' '; GAME_WIDTH]; GAME_HEIGHT],
+            buffers: [
+                vec![vec![' '; GAME_WIDTH]; GAME_HEIGHT],
+                vec![vec![' '; GAME_WIDTH]; GAME_HEIGHT],
+            ],
+            switch: false,
+            force_repaint: true,
             ground_offset: 0,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            input_log: vec![],
         }
     }
 
-    fn update(&mut self) {
-        let now = Instant::now();
-        if now.duration_since(self.last_update) < Duration::from_millis(GAME_SPEED) {
-            return;
+    fn buffer(&self) -> &Vec<Vec<char>> {
+        &self.buffers[self.switch as usize]
+    }
+
+    fn buffer_mut(&mut self) -> &mut Vec<Vec<char>> {
+        &mut self.buffers[self.switch as usize]
+    }
+
+    fn prev_buffer(&self) -> &Vec<Vec<char>> {
+        &self.buffers[!self.switch as usize]
+    }
+
+    // Advances the game by exactly one tick given this tick's input. Game
+    // state depends only on (seed, tick, input) and never on wall-clock
+    // time, so replaying the recorded input log reproduces the run exactly.
+    fn update(&mut self, input: Input) {
+        self.input_log.push(input);
+
+        let jump_held = matches!(input, Input::Jump);
+        if jump_held && self.plane_y == 0 && !self.boosting {
+            self.boosting = true;
+            self.boost_ticks = 0;
         }
-        self.last_update = now;
 
-        if self.jumping {
-            self.plane_y = self.plane_y.saturating_add(1);
-            if self.plane_y >= JUMP_HEIGHT {
-                self.jumping = false;
+        if self.boosting {
+            self.velocity = BOOST_VELOCITY;
+            self.boost_ticks += 1;
+            // A tap still guarantees MIN_BOOST_TICKS of lift even after the
+            // key is released; holding past MAX_BOOST_TICKS forces the
+            // boost to end so gravity can take back over.
+            let held_long_enough = self.boost_ticks >= MIN_BOOST_TICKS;
+            let held_too_long = self.boost_ticks >= MAX_BOOST_TICKS;
+            if held_too_long || (!jump_held && held_long_enough) {
+                self.boosting = false;
             }
-        } else if self.plane_y > 0 {
-            self.plane_y = self.plane_y.saturating_sub(1);
+        } else {
+            self.velocity -= GRAVITY;
+        }
+
+        self.plane_y = (self.plane_y + self.velocity).clamp(0, MAX_PLANE_HEIGHT);
+        if self.plane_y == 0 {
+            self.velocity = 0;
         }
 
         // Move ground from right to left
@@ -69,17 +179,45 @@ impl Game {
         self.obstacles.retain(|&(x, _)| x > 0);
 
         // Spawn new obstacles on the right side
-        if rand::thread_rng().gen_ratio(1, 20) && !self.obstacles.iter().any(|&(x, _)| x == GAME_WIDTH - 1) {
-            let building = *BUILDINGS.choose(&mut rand::thread_rng()).unwrap();
+        if self.rng.gen_ratio(1, 20) && !self.obstacles.iter().any(|&(x, _)| x == GAME_WIDTH - 1) {
+            let building = *BUILDINGS.choose(&mut self.rng).unwrap();
             self.obstacles.push((GAME_WIDTH - 1, building));
         }
 
         self.score += 1;
     }
 
+    // Writes the seed and recorded input log so the run can be replayed
+    // exactly with `--replay <file>`.
+    fn save_replay(&self, path: &str) -> std::io::Result<()> {
+        let mut contents = String::new();
+        contents.push_str(&self.seed.to_string());
+        contents.push('\n');
+        for input in &self.input_log {
+            contents.push(input.to_char());
+            contents.push('\n');
+        }
+        fs::write(path, contents)
+    }
+
+    // Reads back a seed plus input log written by `save_replay`.
+    fn load_replay(path: &str) -> std::io::Result<(u64, Vec<Input>)> {
+        let contents = fs::read_to_string(path)?;
+        let mut lines = contents.lines();
+        let seed = lines
+            .next()
+            .and_then(|line| line.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        let inputs = lines
+            .filter_map(|line| line.trim().chars().next())
+            .map(Input::from_char)
+            .collect();
+        Ok((seed, inputs))
+    }
+
     fn draw(&mut self) {
-        // Clear buffer
-        for row in self.buffer.iter_mut() {
+        // Clear the front buffer
+        for row in self.buffer_mut().iter_mut() {
             for cell in row.iter_mut() {
                 *cell = ' ';
             }
@@ -88,31 +226,48 @@ impl Game {
         // Draw ground
         for x in 0..GAME_WIDTH {
             let ground_x = (x + self.ground_offset) % GAME_WIDTH;
-            self.buffer[GAME_HEIGHT - 1][ground_x] = GROUND;
+            self.buffer_mut()[GAME_HEIGHT - 1][ground_x] = GROUND;
         }
 
         // Draw plane on the left side
-        let plane_y = GAME_HEIGHT - 2 - self.plane_y;
-        self.buffer[plane_y][2] = PLANE;
+        let plane_row = (GAME_HEIGHT - 2) - self.plane_y as usize;
+        self.buffer_mut()[plane_row][PLANE_X] = PLANE;
 
-        // Draw obstacles (buildings)
+        // Draw obstacles (buildings). Indexes `self.buffers` directly (a
+        // field projection) rather than going through `buffer_mut()`, since
+        // a `&self.obstacles` borrow is held across the whole loop and
+        // `buffer_mut()` would need a conflicting `&mut self`.
+        let switch = self.switch as usize;
         for &(x, building) in &self.obstacles {
             if x < GAME_WIDTH {
-                self.buffer[GAME_HEIGHT - 2][x] = building.chars().next().unwrap();
+                self.buffers[switch][GAME_HEIGHT - 2][x] = building.chars().next().unwrap();
             }
         }
     }
 
-    fn render(&self) -> crossterm::Result<()> {
+    // Same as `draw`, but also marks the opponent's plane at its current
+    // height so a multiplayer match can show both players on one screen.
+    fn draw_with_opponent(&mut self, opponent_y: i32) {
+        self.draw();
+        let opponent_row = (GAME_HEIGHT - 2) - opponent_y.clamp(0, MAX_PLANE_HEIGHT) as usize;
+        self.buffer_mut()[opponent_row][OPPONENT_X] = OPPONENT_PLANE;
+    }
+
+    fn render(&mut self) -> crossterm::Result<()> {
         let mut stdout = stdout();
-        execute!(stdout, Hide, MoveTo(0, 0))?;
+        execute!(stdout, Hide)?;
 
-        for (i, row) in self.buffer.iter().enumerate() {
-            execute!(
-                stdout,
-                MoveTo(0, i as u16),
-                Print(row.iter().collect::<String>())
-            )?;
+        if self.force_repaint {
+            execute!(stdout, Clear(ClearType::All))?;
+        }
+
+        for y in 0..GAME_HEIGHT {
+            for x in 0..GAME_WIDTH {
+                let cell = self.buffer()[y][x];
+                if self.force_repaint || cell != self.prev_buffer()[y][x] {
+                    execute!(stdout, MoveTo(x as u16, y as u16), Print(cell))?;
+                }
+            }
         }
 
         execute!(
@@ -124,28 +279,186 @@ impl Game {
         execute!(stdout, Print("\n"))?;
 
         stdout.flush()?;
+
+        // The frame we just drew becomes next frame's "previous" for diffing.
+        self.switch = !self.switch;
+        self.force_repaint = false;
         Ok(())
     }
 
     fn is_collision(&self) -> bool {
-        self.obstacles.iter().any(|&(x, _)| x == 2 && self.plane_y == 0)
+        self.obstacles.iter().any(|&(x, _)| x == PLANE_X && self.plane_y == 0)
+    }
+
+    /// Normalized inputs for the neuroevolution auto-pilot: plane height,
+    /// plane vertical velocity, distance to the nearest obstacle ahead, and
+    /// whether one is present at all (buildings all share one height).
+    fn ai_inputs(&self) -> [f32; nn::INPUT_SIZE] {
+        let height = self.plane_y as f32 / MAX_PLANE_HEIGHT as f32;
+        let velocity = self.velocity as f32 / BOOST_VELOCITY as f32;
+        let nearest = self
+            .obstacles
+            .iter()
+            .map(|&(x, _)| x)
+            .filter(|&x| x >= PLANE_X)
+            .min();
+        let (distance, present) = match nearest {
+            Some(x) => ((x - PLANE_X) as f32 / GAME_WIDTH as f32, 1.0),
+            None => (1.0, 0.0),
+        };
+        [height, velocity, distance, present]
     }
 }
 
-fn main() -> crossterm::Result<()> {
-    enable_raw_mode()?;
+fn print_game_over(game: &Game) -> crossterm::Result<()> {
     let mut stdout = stdout();
-    execute!(stdout, Clear(ClearType::All))?;
+    execute!(
+        stdout,
+        MoveTo(0, (GAME_HEIGHT + 1) as u16),
+        Clear(ClearType::FromCursorDown),
+        Print(format!("Game Over! Final Score: {}", game.score)),
+        Print("\n\n")
+    )?;
+    stdout.flush()
+}
+
+// Reads a name from the keyboard one keystroke at a time, since the
+// terminal is still in raw mode at this point.
+fn prompt_name() -> crossterm::Result<String> {
+    let mut stdout = stdout();
+    execute!(stdout, Print("Enter your name: "))?;
+    stdout.flush()?;
+
+    let mut name = String::new();
+    loop {
+        if let Event::Key(event) = read()? {
+            match event.code {
+                KeyCode::Enter | KeyCode::Esc => break,
+                KeyCode::Backspace => {
+                    if name.pop().is_some() {
+                        execute!(stdout, MoveLeft(1), Print(' '), MoveLeft(1))?;
+                        stdout.flush()?;
+                    }
+                }
+                KeyCode::Char(c) => {
+                    name.push(c);
+                    execute!(stdout, Print(c))?;
+                    stdout.flush()?;
+                }
+                _ => {}
+            }
+        }
+    }
+    execute!(stdout, Print("\n"))?;
+    Ok(name)
+}
+
+// Best-effort: a leaderboard server that isn't running shouldn't crash the
+// game, so submission and fetch failures are just swallowed.
+fn submit_score(name: &str, points: u32) {
+    let client = reqwest::blocking::Client::new();
+    let _ = client
+        .post(format!("{LEADERBOARD_URL}/scores"))
+        .json(&ScoreSubmission { name, points })
+        .send();
+}
+
+fn fetch_top_scores() -> Vec<LeaderboardEntry> {
+    reqwest::blocking::get(format!("{LEADERBOARD_URL}/scores/top"))
+        .and_then(|response| response.json())
+        .unwrap_or_default()
+}
+
+fn show_leaderboard(scores: &[LeaderboardEntry]) -> crossterm::Result<()> {
+    let mut stdout = stdout();
+    execute!(stdout, Print("\nTop Scores:\n"))?;
+    for (rank, entry) in scores.iter().enumerate() {
+        execute!(
+            stdout,
+            Print(format!("{}. {} - {}\n", rank + 1, entry.name, entry.points))
+        )?;
+    }
+    stdout.flush()
+}
+
+// Live play: input comes from the keyboard, and the run's seed and input
+// log are saved to `REPLAY_LOG_PATH` afterwards so it can be replayed.
+fn run_live() -> crossterm::Result<()> {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let mut game = Game::new(seed);
+    let mut last_tick = Instant::now();
+    let mut pending_input = Input::None;
+
+    loop {
+        if poll(Duration::from_millis(10))? {
+            match read()? {
+                Event::Key(event) => match event.code {
+                    KeyCode::Char(' ') | KeyCode::Up => pending_input = Input::Jump,
+                    KeyCode::Esc | KeyCode::Char('q') => pending_input = Input::Quit,
+                    KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        pending_input = Input::Quit;
+                    }
+                    _ => {}
+                },
+                Event::Resize(_, _) => {
+                    // The terminal was resized; the old frame on screen no longer
+                    // matches our diff buffer, so force a full repaint next frame.
+                    game.force_repaint = true;
+                }
+                _ => {}
+            }
+        }
+
+        if last_tick.elapsed() < Duration::from_millis(GAME_SPEED) {
+            continue;
+        }
+        last_tick = Instant::now();
+
+        if pending_input == Input::Quit {
+            break;
+        }
 
-    let mut game = Game::new();
+        let input = std::mem::replace(&mut pending_input, Input::None);
+        game.update(input);
+        game.draw();
+        game.render()?;
+
+        if game.is_collision() {
+            print_game_over(&game)?;
+
+            let name = prompt_name()?;
+            submit_score(&name, game.score);
+            show_leaderboard(&fetch_top_scores())?;
+            break;
+        }
+    }
+
+    // Best-effort: a failure to save the replay shouldn't stop the player
+    // from quitting cleanly.
+    let _ = game.save_replay(REPLAY_LOG_PATH);
+    Ok(())
+}
+
+// Auto-pilot: a trained network picks the input each tick instead of the
+// keyboard, so the player can just watch it fly.
+fn run_ai(genome_path: &str) -> crossterm::Result<()> {
+    let genome = trainer::load_genome(genome_path)?;
+    let network = nn::Network::new(&genome);
+
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let mut game = Game::new(seed);
+    let mut last_tick = Instant::now();
 
     loop {
         if poll(Duration::from_millis(10))? {
             if let Event::Key(event) = read()? {
                 match event.code {
-                    KeyCode::Char(' ') | KeyCode::Up if !game.jumping && game.plane_y == 0 => {
-                        game.jumping = true;
-                    }
                     KeyCode::Esc | KeyCode::Char('q') => break,
                     KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => break,
                     _ => {}
@@ -153,23 +466,185 @@ fn main() -> crossterm::Result<()> {
             }
         }
 
-        game.update();
+        if last_tick.elapsed() < Duration::from_millis(GAME_SPEED) {
+            continue;
+        }
+        last_tick = Instant::now();
+
+        let input = if network.wants_jump(game.ai_inputs()) {
+            Input::Jump
+        } else {
+            Input::None
+        };
+        game.update(input);
         game.draw();
         game.render()?;
 
         if game.is_collision() {
-            execute!(
-                stdout,
-                MoveTo(0, (GAME_HEIGHT + 1) as u16),
-                Clear(ClearType::FromCursorDown),
-                Print(format!("Game Over! Final Score: {}", game.score)),
-                Print("\n\n")
-            )?;
-            stdout.flush()?;
+            print_game_over(&game)?;
             break;
         }
     }
 
+    Ok(())
+}
+
+// Trains a population of genomes headlessly (no rendering) and persists the
+// best one, ready to be loaded back with `--ai <genome-file>`.
+fn run_training(output_path: &str) -> crossterm::Result<()> {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let config = trainer::TrainerConfig::default();
+    let best_genome = trainer::train(&config, seed);
+    trainer::save_genome(&best_genome, output_path)?;
+    println!("Saved best genome to {output_path}");
+    Ok(())
+}
+
+// Replay: input comes from a previously saved seed + input log instead of
+// the keyboard, so the exact same run plays back deterministically.
+fn run_replay(path: &str) -> crossterm::Result<()> {
+    let (seed, inputs) = Game::load_replay(path)?;
+    let mut game = Game::new(seed);
+
+    for input in inputs {
+        if input == Input::Quit {
+            break;
+        }
+
+        std::thread::sleep(Duration::from_millis(GAME_SPEED));
+        game.update(input);
+        game.draw();
+        game.render()?;
+
+        if game.is_collision() {
+            print_game_over(&game)?;
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// Two-player lockstep: both sides run their own deterministic `Game` from
+// the same seed and only ever exchange per-tick `Input`, so the two
+// simulations stay in lockstep without any game state crossing the wire.
+fn run_multiplayer() -> crossterm::Result<()> {
+    let mut session = net::MultiplayerSession::connect(MATCHMAKING_URL)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+
+    let mut local_game = Game::new(session.seed);
+    let mut peer_game = Game::new(session.seed);
+    let mut tick: u64 = 0;
+    let mut last_tick = Instant::now();
+    // Mirror hashes not yet matched against the opponent's hash for the
+    // same tick, oldest first.
+    let mut peer_mirror_hashes = std::collections::VecDeque::new();
+
+    loop {
+        let mut input = Input::None;
+        if poll(Duration::from_millis(10))? {
+            match read()? {
+                Event::Key(event) => match event.code {
+                    KeyCode::Char(' ') | KeyCode::Up => input = Input::Jump,
+                    KeyCode::Esc | KeyCode::Char('q') => input = Input::Quit,
+                    KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        input = Input::Quit;
+                    }
+                    _ => {}
+                },
+                Event::Resize(_, _) => local_game.force_repaint = true,
+                _ => {}
+            }
+        }
+
+        if last_tick.elapsed() < Duration::from_millis(GAME_SPEED) {
+            continue;
+        }
+        last_tick = Instant::now();
+
+        let local_input = session
+            .exchange_local_input(input)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        let peer_input = session
+            .recv_peer_input()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+
+        if local_input == Input::Quit || peer_input == Input::Quit {
+            break;
+        }
+
+        local_game.update(local_input);
+        peer_game.update(peer_input);
+        tick += 1;
+
+        // Send the authoritative hash of our own plane, for the opponent to
+        // check their mirror of us against. In return, check the hash they
+        // send of *their* own plane against our `peer_game` mirror of it —
+        // comparing it against our own `local_game` would just be comparing
+        // two independently-controlled planes, which differ on nearly every
+        // tick regardless of any real desync.
+        let local_hash = net::state_hash(tick, local_game.plane_y, local_game.velocity, local_game.score);
+        session
+            .send_state_hash(local_hash)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        peer_mirror_hashes.push_back(net::state_hash(tick, peer_game.plane_y, peer_game.velocity, peer_game.score));
+        while let Some(&peer_mirror_hash) = peer_mirror_hashes.front() {
+            let received_hash = match session.try_recv_state_hash() {
+                Some(hash) => hash,
+                None => break,
+            };
+            peer_mirror_hashes.pop_front();
+            if received_hash != peer_mirror_hash {
+                execute!(
+                    stdout(),
+                    MoveTo(0, (GAME_HEIGHT + 1) as u16),
+                    Print("Desync detected with opponent!\n")
+                )?;
+            }
+        }
+
+        local_game.draw_with_opponent(peer_game.plane_y);
+        local_game.render()?;
+
+        if local_game.is_collision() || peer_game.is_collision() {
+            print_game_over(&local_game)?;
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn arg_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|arg| arg == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn main() -> crossterm::Result<()> {
+    let args: Vec<String> = env::args().collect();
+
+    // Training is headless, so it never touches the terminal's raw mode.
+    if let Some(output_path) = arg_value(&args, "--train") {
+        return run_training(&output_path);
+    }
+
+    let replay_path = arg_value(&args, "--replay");
+    let ai_genome_path = arg_value(&args, "--ai");
+    let multiplayer = args.iter().any(|arg| arg == "--multiplayer");
+
+    enable_raw_mode()?;
+    let mut stdout = stdout();
+    execute!(stdout, Clear(ClearType::All))?;
+
+    let result = match (replay_path, ai_genome_path, multiplayer) {
+        (Some(path), _, _) => run_replay(&path),
+        (None, Some(path), _) => run_ai(&path),
+        (None, None, true) => run_multiplayer(),
+        (None, None, false) => run_live(),
+    };
+
     execute!(
         stdout,
         Clear(ClearType::FromCursorDown),
@@ -179,5 +654,5 @@ fn main() -> crossterm::Result<()> {
     )?;
     disable_raw_mode()?;
     stdout.flush()?;
-    Ok(())
+    result
 }
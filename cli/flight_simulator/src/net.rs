@@ -0,0 +1,214 @@
+//! Networking for two-player lockstep multiplayer. Because the game is
+//! deterministic from a shared seed, clients only ever need to exchange one
+//! `Input` per tick — never obstacles or scores — and their simulations can
+//! never diverge as long as both apply the same inputs in the same order.
+
+use std::collections::VecDeque;
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::time::Duration;
+
+use tungstenite::{connect, stream::MaybeTlsStream, Error as WsError, Message, WebSocket};
+
+use crate::Input;
+
+/// How many ticks a player's own input is held back before being applied
+/// locally. The input is still sent to the opponent immediately, so this
+/// buffer gives the network a head start to deliver it before it's needed,
+/// hiding latency instead of stalling the game on every tick.
+pub const INPUT_DELAY_TICKS: usize = 3;
+
+/// Message tag identifying the payload that follows it on the wire.
+const TAG_INPUT: u8 = 0;
+const TAG_HASH: u8 = 1;
+
+fn input_to_byte(input: Input) -> u8 {
+    match input {
+        Input::None => 0,
+        Input::Jump => 1,
+        Input::Quit => 2,
+    }
+}
+
+fn byte_to_input(byte: u8) -> Input {
+    match byte {
+        1 => Input::Jump,
+        2 => Input::Quit,
+        _ => Input::None,
+    }
+}
+
+/// A live match with an opponent: the handshake has already happened and
+/// both sides agree on `seed` and who is `slot` 0 versus 1.
+pub struct MultiplayerSession {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    pub seed: u64,
+    pub slot: u8,
+    local_delay_buffer: VecDeque<Input>,
+    // The opponent's raw input stream needs the same padding/delay applied
+    // to it as `local_delay_buffer` applies to our own, so that at any tick
+    // both sides are consuming the same padded, delayed sequence for this
+    // player rather than the opponent seeing it `INPUT_DELAY_TICKS` early.
+    peer_delay_buffer: VecDeque<Input>,
+    // Both message kinds arrive interleaved on the same socket, so every
+    // read is routed into one of these queues by its tag instead of being
+    // inspected-then-discarded by whichever call happened to read it.
+    pending_inputs: VecDeque<Input>,
+    pending_hashes: VecDeque<u64>,
+}
+
+impl MultiplayerSession {
+    /// Connects to the matchmaking server and blocks until paired with an
+    /// opponent, returning the shared seed and this client's player slot.
+    pub fn connect(url: &str) -> Result<Self, String> {
+        let (mut socket, _) = connect(url).map_err(|e| e.to_string())?;
+
+        let handshake = loop {
+            match socket.read_message().map_err(|e| e.to_string())? {
+                Message::Text(text) => break text,
+                _ => continue,
+            }
+        };
+        let mut parts = handshake.split_whitespace();
+        let seed: u64 = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or("malformed seed in handshake")?;
+        let slot: u8 = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or("malformed slot in handshake")?;
+
+        // Slot 0 gets its handshake before anyone else has joined; the
+        // server sends "matched" once the opponent arrives.
+        if slot == 0 {
+            loop {
+                match socket.read_message().map_err(|e| e.to_string())? {
+                    Message::Text(text) if text == "matched" => break,
+                    _ => continue,
+                }
+            }
+        }
+
+        // The handshake read above is the last blocking read we want: after
+        // this, `try_recv_state_hash` needs to be able to poll the socket
+        // without stalling the game loop when nothing has arrived yet.
+        if let MaybeTlsStream::Plain(tcp) = socket.get_ref() {
+            tcp.set_nonblocking(true).map_err(|e| e.to_string())?;
+        }
+
+        Ok(MultiplayerSession {
+            socket,
+            seed,
+            slot,
+            local_delay_buffer: std::iter::repeat(Input::None).take(INPUT_DELAY_TICKS).collect(),
+            peer_delay_buffer: std::iter::repeat(Input::None).take(INPUT_DELAY_TICKS).collect(),
+            pending_inputs: VecDeque::new(),
+            pending_hashes: VecDeque::new(),
+        })
+    }
+
+    /// Reads every message currently available on the socket without
+    /// blocking, routing each into the input or hash queue by its tag.
+    /// Used by both `recv_peer_input` and `try_recv_state_hash` so neither
+    /// call can read and discard a message meant for the other.
+    fn pump(&mut self) -> Result<(), String> {
+        loop {
+            match self.socket.read_message() {
+                Ok(Message::Binary(bytes)) => match bytes.first() {
+                    Some(&TAG_INPUT) => {
+                        self.pending_inputs.push_back(byte_to_input(bytes.get(1).copied().unwrap_or(0)));
+                    }
+                    Some(&TAG_HASH) => {
+                        if let Some(hash_bytes) = bytes.get(1..9).and_then(|b| b.try_into().ok()) {
+                            self.pending_hashes.push_back(u64::from_le_bytes(hash_bytes));
+                        }
+                    }
+                    _ => {}
+                },
+                Ok(Message::Close(_)) => {
+                    self.pending_inputs.push_back(Input::Quit);
+                }
+                Ok(_) => {}
+                Err(WsError::Io(ref e)) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
+
+    /// Ships this tick's local input to the opponent right away, and
+    /// returns the input that should actually be applied locally this
+    /// tick — held back by `INPUT_DELAY_TICKS` so the opponent's copy has
+    /// time to arrive before either side needs it.
+    pub fn exchange_local_input(&mut self, input: Input) -> Result<Input, String> {
+        self.write_message(Message::Binary(vec![TAG_INPUT, input_to_byte(input)]))?;
+        self.local_delay_buffer.push_back(input);
+        Ok(self.local_delay_buffer.pop_front().unwrap_or(Input::None))
+    }
+
+    /// Returns the input that should actually be applied to this tick's
+    /// `peer_game` — held back by `INPUT_DELAY_TICKS` in the same way as
+    /// `exchange_local_input`, so both sides apply the identical
+    /// padded/delayed sequence for this player rather than one side running
+    /// `INPUT_DELAY_TICKS` ahead of the other. Blocks only while that
+    /// delayed input has not arrived yet.
+    pub fn recv_peer_input(&mut self) -> Result<Input, String> {
+        loop {
+            self.pump()?;
+            self.peer_delay_buffer.extend(self.pending_inputs.drain(..));
+            if let Some(input) = self.peer_delay_buffer.pop_front() {
+                return Ok(input);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Sends this tick's desync-check hash to the opponent.
+    pub fn send_state_hash(&mut self, hash: u64) -> Result<(), String> {
+        let mut payload = vec![TAG_HASH];
+        payload.extend_from_slice(&hash.to_le_bytes());
+        self.write_message(Message::Binary(payload))
+    }
+
+    /// Non-blocking: returns the opponent's oldest unread hash if one has
+    /// arrived, so a desync can be reported without stalling the game loop
+    /// waiting for it. Hashes come back in the order they were sent, one
+    /// per tick, so the n-th hash returned is the one for tick n.
+    pub fn try_recv_state_hash(&mut self) -> Option<u64> {
+        let _ = self.pump();
+        self.pending_hashes.pop_front()
+    }
+
+    /// Writes a message, retrying while the non-blocking socket reports its
+    /// send buffer is momentarily full instead of treating that as a
+    /// failure.
+    fn write_message(&mut self, message: Message) -> Result<(), String> {
+        loop {
+            match self.socket.write_message(message.clone()) {
+                Ok(()) => return Ok(()),
+                Err(WsError::Io(ref e)) if e.kind() == ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
+}
+
+/// A small FNV-1a hash over the state both sims should agree on for a given
+/// tick. Comparing these between peers is the desync check: if they ever
+/// differ, the two "deterministic" simulations have drifted apart.
+pub fn state_hash(tick: u64, plane_y: i32, velocity: i32, score: u32) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let bytes = tick
+        .to_le_bytes()
+        .into_iter()
+        .chain(plane_y.to_le_bytes())
+        .chain(velocity.to_le_bytes())
+        .chain(score.to_le_bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
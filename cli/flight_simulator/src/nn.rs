@@ -0,0 +1,57 @@
+//! A tiny feed-forward network used as the neuroevolution auto-pilot's
+//! control policy. A genome is just a flat vector of weights and biases for
+//! two dense layers; `Network` is the view that knows how to run it forward.
+
+/// plane height, plane vertical velocity, distance to the nearest obstacle
+/// ahead, and whether an obstacle is present (buildings share one height).
+pub const INPUT_SIZE: usize = 4;
+pub const HIDDEN_SIZE: usize = 8;
+pub const OUTPUT_SIZE: usize = 1;
+pub const GENOME_LEN: usize =
+    INPUT_SIZE * HIDDEN_SIZE + HIDDEN_SIZE + HIDDEN_SIZE * OUTPUT_SIZE + OUTPUT_SIZE;
+
+/// Activation above this threshold means "jump" for that tick.
+pub const JUMP_THRESHOLD: f32 = 0.0;
+
+pub struct Network<'a> {
+    genome: &'a [f32],
+}
+
+impl<'a> Network<'a> {
+    pub fn new(genome: &'a [f32]) -> Self {
+        assert_eq!(
+            genome.len(),
+            GENOME_LEN,
+            "genome has {} weights, expected {GENOME_LEN}",
+            genome.len()
+        );
+        Network { genome }
+    }
+
+    /// Runs the network forward and returns the single output activation.
+    pub fn activate(&self, inputs: [f32; INPUT_SIZE]) -> f32 {
+        let (w1, rest) = self.genome.split_at(INPUT_SIZE * HIDDEN_SIZE);
+        let (b1, rest) = rest.split_at(HIDDEN_SIZE);
+        let (w2, b2) = rest.split_at(HIDDEN_SIZE * OUTPUT_SIZE);
+
+        let mut hidden = [0.0f32; HIDDEN_SIZE];
+        for (h, slot) in hidden.iter_mut().enumerate() {
+            let mut sum = b1[h];
+            for (i, &input) in inputs.iter().enumerate() {
+                sum += input * w1[h * INPUT_SIZE + i];
+            }
+            *slot = sum.tanh();
+        }
+
+        let mut output = b2[0];
+        for (h, &value) in hidden.iter().enumerate() {
+            output += value * w2[h];
+        }
+        output
+    }
+
+    /// Whether this tick's activation clears the jump threshold.
+    pub fn wants_jump(&self, inputs: [f32; INPUT_SIZE]) -> bool {
+        self.activate(inputs) > JUMP_THRESHOLD
+    }
+}
@@ -0,0 +1,166 @@
+//! Genetic-algorithm trainer for the neuroevolution auto-pilot: evaluate a
+//! population of genomes headlessly against the deterministic game, then
+//! breed the next generation from the fittest by elitism, tournament
+//! selection, uniform crossover, and Gaussian mutation.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::nn::{Network, GENOME_LEN};
+use crate::{Game, Input};
+
+pub struct TrainerConfig {
+    pub population: usize,
+    pub generations: usize,
+    pub elitism: usize,
+    pub tournament_size: usize,
+    pub mutation_rate: f32,
+    pub mutation_sigma: f32,
+    pub max_ticks: u64,
+}
+
+impl Default for TrainerConfig {
+    fn default() -> Self {
+        TrainerConfig {
+            population: 64,
+            generations: 40,
+            elitism: 4,
+            tournament_size: 4,
+            mutation_rate: 0.1,
+            mutation_sigma: 0.3,
+            max_ticks: 5_000,
+        }
+    }
+}
+
+/// Runs a genome through one headless game and returns its fitness (ticks
+/// survived before colliding, or `max_ticks` if it never crashes).
+fn evaluate(genome: &[f32], seed: u64, max_ticks: u64) -> u64 {
+    let network = Network::new(genome);
+    let mut game = Game::new(seed);
+
+    for tick in 0..max_ticks {
+        let input = if network.wants_jump(game.ai_inputs()) {
+            Input::Jump
+        } else {
+            Input::None
+        };
+        game.update(input);
+        if game.is_collision() {
+            return tick;
+        }
+    }
+    max_ticks
+}
+
+fn random_genome(rng: &mut StdRng) -> Vec<f32> {
+    (0..GENOME_LEN).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+fn tournament_select<'a>(scored: &'a [(Vec<f32>, u64)], rng: &mut StdRng, size: usize) -> &'a [f32] {
+    let mut best = &scored[rng.gen_range(0..scored.len())];
+    for _ in 1..size {
+        let candidate = &scored[rng.gen_range(0..scored.len())];
+        if candidate.1 > best.1 {
+            best = candidate;
+        }
+    }
+    &best.0
+}
+
+fn crossover(a: &[f32], b: &[f32], rng: &mut StdRng) -> Vec<f32> {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&x, &y)| if rng.gen_bool(0.5) { x } else { y })
+        .collect()
+}
+
+fn mutate(genome: &mut [f32], rng: &mut StdRng, rate: f32, sigma: f32) {
+    for gene in genome.iter_mut() {
+        if rng.gen_bool(rate as f64) {
+            // Sum of uniforms approximates a Gaussian without pulling in a
+            // distributions crate. Three U(-1, 1) draws sum to unit
+            // variance, so `noise * sigma` has standard deviation `sigma`.
+            let noise: f32 = (0..3).map(|_| rng.gen_range(-1.0..1.0)).sum::<f32>();
+            *gene += noise * sigma;
+        }
+    }
+}
+
+/// Evolves a population of genomes and returns the best one found, printing
+/// each generation's top fitness so progress is visible on the terminal.
+pub fn train(config: &TrainerConfig, seed: u64) -> Vec<f32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut population: Vec<Vec<f32>> =
+        (0..config.population).map(|_| random_genome(&mut rng)).collect();
+
+    let mut best_genome = population[0].clone();
+    let mut best_fitness = 0u64;
+
+    for generation in 0..config.generations {
+        let mut scored: Vec<(Vec<f32>, u64)> = population
+            .into_iter()
+            .enumerate()
+            .map(|(i, genome)| {
+                // Vary the evaluation seed per genome/generation so a genome
+                // can't overfit a single obstacle sequence.
+                let eval_seed = seed
+                    .wrapping_add(generation as u64 * config.population as u64)
+                    .wrapping_add(i as u64);
+                let fitness = evaluate(&genome, eval_seed, config.max_ticks);
+                (genome, fitness)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+
+        if scored[0].1 > best_fitness {
+            best_fitness = scored[0].1;
+            best_genome = scored[0].0.clone();
+        }
+        println!("generation {generation}: best fitness = {}", scored[0].1);
+
+        let mut next_generation = Vec::with_capacity(config.population);
+        next_generation.extend(scored.iter().take(config.elitism).map(|(g, _)| g.clone()));
+
+        while next_generation.len() < config.population {
+            let parent_a = tournament_select(&scored, &mut rng, config.tournament_size);
+            let parent_b = tournament_select(&scored, &mut rng, config.tournament_size);
+            let mut child = crossover(parent_a, parent_b, &mut rng);
+            mutate(&mut child, &mut rng, config.mutation_rate, config.mutation_sigma);
+            next_generation.push(child);
+        }
+
+        population = next_generation;
+    }
+
+    best_genome
+}
+
+/// Genomes are small, so a single line of whitespace-separated floats is a
+/// plenty simple on-disk format.
+pub fn save_genome(genome: &[f32], path: &str) -> std::io::Result<()> {
+    let contents = genome.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(" ");
+    std::fs::write(path, contents)
+}
+
+pub fn load_genome(path: &str) -> std::io::Result<Vec<f32>> {
+    let contents = std::fs::read_to_string(path)?;
+    let genome: Vec<f32> = contents
+        .split_whitespace()
+        .filter_map(|token| token.parse::<f32>().ok())
+        .collect();
+
+    // A malformed or truncated file would otherwise reach `Network::new`'s
+    // `assert_eq!` on the genome length and panic mid-game, unwinding past
+    // the raw-mode cleanup in `main` and leaving the terminal stuck.
+    if genome.len() != GENOME_LEN {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "genome file {path} has {} usable weights, expected {GENOME_LEN}",
+                genome.len()
+            ),
+        ));
+    }
+
+    Ok(genome)
+}
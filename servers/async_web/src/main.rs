@@ -1,7 +1,12 @@
-use warp::{Filter, Rejection, Reply};
+use futures_util::{SinkExt, StreamExt};
+use rand::Rng;
 use serde_derive::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
 use tokio::time::sleep;
+use warp::ws::{Message, WebSocket};
+use warp::{Filter, Rejection, Reply};
 
 #[derive(Serialize, Deserialize)]
 struct User {
@@ -9,8 +14,25 @@ struct User {
     name: String,
 }
 
+/// Filled in once a player's opponent is known, so the player's forwarding
+/// loop (already running by then) can start relaying to them.
+type OpponentSlot = Arc<Mutex<Option<mpsc::UnboundedSender<Message>>>>;
+
+/// The first player to connect to `/play` waits here, holding the seed
+/// they were assigned, until a second player shows up to complete the
+/// match. Only one match can be pending at a time.
+struct WaitingPlayer {
+    seed: u64,
+    sender: mpsc::UnboundedSender<Message>,
+    opponent_slot: OpponentSlot,
+}
+
+type Lobby = Arc<Mutex<Option<WaitingPlayer>>>;
+
 #[tokio::main]
 async fn main() {
+    let lobby: Lobby = Arc::new(Mutex::new(None));
+
     // Define routes
     let hello = warp::path!("hello" / String)
         .map(|name| format!("Hello, {}!", name));
@@ -22,7 +44,12 @@ async fn main() {
     let delayed = warp::path!("delayed" / u64)
         .and_then(delayed_response);
 
-    let routes = hello.or(users).or(delayed);
+    let play = warp::path("play")
+        .and(warp::ws())
+        .and(warp::any().map(move || lobby.clone()))
+        .map(|ws: warp::ws::Ws, lobby: Lobby| ws.on_upgrade(move |socket| match_players(socket, lobby)));
+
+    let routes = hello.or(users).or(delayed).or(play);
 
     println!("Server starting on http://localhost:3030");
     warp::serve(routes)
@@ -42,3 +69,63 @@ async fn delayed_response(seconds: u64) -> Result<impl Reply, Rejection> {
     sleep(Duration::from_secs(seconds)).await;
     Ok(format!("Response after {} second(s)", seconds))
 }
+
+// Pairs two players into a lockstep match and relays their messages to each
+// other verbatim. Because the game is deterministic from a shared seed, the
+// server never needs to understand (or serialize) game state: it is purely
+// a rendezvous point and a pipe between the two per-tick input streams.
+async fn match_players(ws: WebSocket, lobby: Lobby) {
+    let (mut sink, mut stream) = ws.split();
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Message>();
+    let opponent_slot: OpponentSlot = Arc::new(Mutex::new(None));
+
+    let (seed, slot) = {
+        let mut waiting = lobby.lock().await;
+        match waiting.take() {
+            None => {
+                // First player: wait in the lobby for an opponent. Our own
+                // `opponent_slot` gets filled in by whoever joins next.
+                let seed = rand::thread_rng().gen();
+                *waiting = Some(WaitingPlayer {
+                    seed,
+                    sender: sender.clone(),
+                    opponent_slot: opponent_slot.clone(),
+                });
+                (seed, 0u8)
+            }
+            Some(player) => {
+                // Second player: we know our opponent immediately, and we
+                // complete the match by handing them our sender too.
+                *opponent_slot.lock().await = Some(player.sender.clone());
+                *player.opponent_slot.lock().await = Some(sender.clone());
+                let _ = player.sender.send(Message::text("matched"));
+                (player.seed, 1u8)
+            }
+        }
+    };
+
+    if sink
+        .send(Message::text(format!("{seed} {slot}")))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    let forward = tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            if sink.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(message)) = stream.next().await {
+        let opponent = opponent_slot.lock().await.clone();
+        if let Some(opponent) = opponent {
+            let _ = opponent.send(message);
+        }
+    }
+
+    forward.abort();
+}
@@ -1,5 +1,6 @@
 use actix_web::{get, post, web, App, HttpResponse, HttpServer, Responder};
 use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 
 #[derive(Serialize, Deserialize)]
 struct User {
@@ -7,6 +8,29 @@ struct User {
     age: u32,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+struct Score {
+    name: String,
+    points: u32,
+}
+
+const TOP_SCORES_LIMIT: usize = 10;
+
+/// In-memory leaderboard shared across requests via `web::Data`. A future
+/// pass can swap the `Mutex<Vec<Score>>` for a file-backed store without
+/// touching the route handlers.
+struct Leaderboard {
+    scores: Mutex<Vec<Score>>,
+}
+
+impl Leaderboard {
+    fn new() -> Self {
+        Leaderboard {
+            scores: Mutex::new(Vec::new()),
+        }
+    }
+}
+
 #[get("/")]
 async fn hello() -> impl Responder {
     HttpResponse::Ok().body("Hello, World!")
@@ -27,13 +51,32 @@ async fn get_user(path: web::Path<u32>) -> impl Responder {
     HttpResponse::Ok().json(user)
 }
 
+#[post("/scores")]
+async fn submit_score(leaderboard: web::Data<Leaderboard>, score: web::Json<Score>) -> impl Responder {
+    leaderboard.scores.lock().unwrap().push(score.into_inner());
+    HttpResponse::Ok().finish()
+}
+
+#[get("/scores/top")]
+async fn top_scores(leaderboard: web::Data<Leaderboard>) -> impl Responder {
+    let mut scores = leaderboard.scores.lock().unwrap().clone();
+    scores.sort_by(|a, b| b.points.cmp(&a.points));
+    scores.truncate(TOP_SCORES_LIMIT);
+    HttpResponse::Ok().json(scores)
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    HttpServer::new(|| {
+    let leaderboard = web::Data::new(Leaderboard::new());
+
+    HttpServer::new(move || {
         App::new()
+            .app_data(leaderboard.clone())
             .service(hello)
             .service(echo)
             .service(get_user)
+            .service(submit_score)
+            .service(top_scores)
     })
     .bind(("127.0.0.1", 8080))?
     .run()